@@ -2,11 +2,15 @@ use std::io::Write;
 
 use rand::Rng;
 use rayon::{
-    prelude::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator},
+    prelude::{
+        IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator,
+        ParallelIterator,
+    },
     slice::ParallelSliceMut,
 };
 use raytracer::{
-    get_closest_object, ray_trace, Camera, Material, Object, BOUNCES, SAMPLES_PER_BOUNCE,
+    get_closest_object, load_obj, ray_trace, Bvh, Camera, Material, Object, BOUNCES,
+    SAMPLES_PER_BOUNCE, TONE_MAPPING,
 };
 use thallium::{
     math::{Matrix4x4, One, Vector2, Vector3, Zero},
@@ -78,14 +82,15 @@ void main() {
         )
     };
 
-    let (mut pixels, texture) = {
+    let (mut pixels, mut display_pixels, texture) = {
         let size @ Vector2 {
             x: width,
             y: height,
         } = renderer.get_surface_mut().get_size();
         let pixels = vec![Vector3::zero(); width * height];
+        let display_pixels = vec![Vector3::zero(); width * height];
         let texture = renderer.create_texture(size, Pixels::RGBF(&pixels));
-        (pixels, texture)
+        (pixels, display_pixels, texture)
     };
 
     let mut camera = Camera {
@@ -93,6 +98,10 @@ void main() {
         right: (1.0, 0.0, 0.0).into(),
         up: (0.0, 1.0, 0.0).into(),
         forward: (0.0, 0.0, 1.0).into(),
+        aperture: 0.0,
+        focus_distance: 3.4,
+        shutter_open: 0.0,
+        shutter_close: 1.0,
     };
 
     let mut objects = vec![
@@ -103,15 +112,20 @@ void main() {
                 diffuse_color: (0.2, 0.8, 0.3).into(),
                 emit_color: (0.0, 0.0, 0.0).into(),
                 reflectiveness: 0.0,
+                dielectric: None,
             },
         },
-        Object::Sphere {
-            center: (-1.0, 1.0, 0.0).into(),
+        Object::MovingSphere {
+            center0: (-1.0, 1.0, 0.0).into(),
+            center1: (-1.0, 1.2, 0.0).into(),
+            time0: 0.0,
+            time1: 1.0,
             radius: 1.0,
             material: Material {
                 diffuse_color: (0.8, 0.3, 0.2).into(),
                 emit_color: (0.0, 0.0, 0.0).into(),
                 reflectiveness: 0.0,
+                dielectric: None,
             },
         },
         Object::Sphere {
@@ -120,10 +134,13 @@ void main() {
             material: Material {
                 diffuse_color: (0.95, 0.95, 0.95).into(),
                 emit_color: (0.0, 0.0, 0.0).into(),
-                reflectiveness: 0.95,
+                reflectiveness: 0.0,
+                dielectric: Some(1.5),
             },
         },
     ];
+    objects.extend(load_obj("assets/glass_pyramid.obj"));
+    let mut bvh = Bvh::build(&objects);
 
     let mut frames_since_movement = 0usize;
 
@@ -154,6 +171,7 @@ void main() {
                 ) => {
                     renderer.resize(size);
                     pixels = vec![Vector3::zero(); width * height];
+                    display_pixels = vec![Vector3::zero(); width * height];
                     frames_since_movement = 0;
                 }
                 SurfaceEvent::MousePressed(button, Vector2 { x, y }) => 'mouse_press_handling: {
@@ -163,11 +181,11 @@ void main() {
 
                     let coord = (x as usize, height - y as usize - 1).into();
                     let uv = Camera::get_uv(coord, size);
-                    let ray = camera.get_ray(uv, aspect);
+                    let ray = camera.get_ray(uv, aspect, &mut rand::thread_rng());
 
                     match button {
                         MouseButton::Left => {
-                            if let Some(hit) = get_closest_object(ray, &objects) {
+                            if let Some(hit) = get_closest_object(ray, &objects, &bvh) {
                                 objects.push(Object::Sphere {
                                     center: hit.position + hit.normal * 0.5.into(),
                                     radius: 0.5,
@@ -175,8 +193,10 @@ void main() {
                                         diffuse_color: (0.0, 0.0, 0.0).into(),
                                         emit_color: (3.0, 3.0, 3.0).into(),
                                         reflectiveness: 0.0,
+                                        dielectric: None,
                                     },
                                 });
+                                bvh = Bvh::build(&objects);
                                 frames_since_movement = 0;
                             }
                         }
@@ -243,13 +263,20 @@ void main() {
                             x: (x as f32 + rng.gen::<f32>() * 2.0 - 1.0) / width as f32,
                             y: (y as f32 + rng.gen::<f32>() * 2.0 - 1.0) / height as f32,
                         };
-                        let ray = camera.get_ray(uv, aspect);
-                        color += ray_trace(ray, &objects, &mut rng, BOUNCES);
+                        let ray = camera.get_ray_dof(uv, aspect, &mut rng);
+                        color += ray_trace(ray, &objects, &bvh, &mut rng, BOUNCES);
                     }
                     color *= (1.0 / SAMPLES_PER_BOUNCE as f32).into();
 
                     *pixel += (color - *pixel) / (frames_since_movement as f32 + 1.0).into();
                 });
+
+            display_pixels
+                .par_iter_mut()
+                .zip(pixels.par_iter())
+                .for_each(|(display_pixel, pixel)| {
+                    *display_pixel = TONE_MAPPING.apply(*pixel);
+                });
         }
 
         // Render to window
@@ -258,7 +285,7 @@ void main() {
             renderer
                 .get_texture_mut(texture)
                 .unwrap()
-                .set_pixels(size, Pixels::RGBF(&pixels));
+                .set_pixels(size, Pixels::RGBF(&display_pixels));
             let mut draw_context = renderer.drawing_context(Default::default(), false);
             draw_context.draw(
                 PrimitiveType::TriangleStrip,
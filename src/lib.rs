@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use rand::Rng;
 use thallium::math::{Vector2, Vector3, Zero};
 
@@ -5,6 +7,7 @@ use thallium::math::{Vector2, Vector3, Zero};
 pub struct Ray {
     pub origin: Vector3<f32>,
     pub direction: Vector3<f32>,
+    pub time: f32,
 }
 
 #[derive(Clone, Copy)]
@@ -12,12 +15,17 @@ pub struct Material {
     pub diffuse_color: Vector3<f32>,
     pub emit_color: Vector3<f32>,
     pub reflectiveness: f32,
+    /// Index of refraction of a dielectric (glass-like) material, or `None`.
+    pub dielectric: Option<f32>,
 }
 
 #[derive(Clone, Copy)]
 pub struct Hit {
     pub position: Vector3<f32>,
+    /// The surface's true geometric normal, never flipped toward the ray.
     pub normal: Vector3<f32>,
+    /// Whether the ray hit the side `normal` points toward.
+    pub front_face: bool,
     pub distance: f32,
 }
 
@@ -33,15 +41,51 @@ pub enum Object {
         distance_along_normal: f32,
         material: Material,
     },
+    Triangle {
+        v0: Vector3<f32>,
+        v1: Vector3<f32>,
+        v2: Vector3<f32>,
+        normal: Vector3<f32>,
+        material: Material,
+    },
+    MovingSphere {
+        center0: Vector3<f32>,
+        center1: Vector3<f32>,
+        time0: f32,
+        time1: f32,
+        radius: f32,
+        material: Material,
+    },
 }
 
 impl Object {
     pub fn get_material(&self) -> &Material {
         match self {
-            Object::Sphere { material, .. } | Object::Plane { material, .. } => material,
+            Object::Sphere { material, .. }
+            | Object::Plane { material, .. }
+            | Object::Triangle { material, .. }
+            | Object::MovingSphere { material, .. } => material,
         }
     }
 
+    /// Linearly interpolates the center between `center0`/`center1`; returns
+    /// `center0` if `time0 == time1` instead of dividing by zero.
+    fn moving_sphere_center(
+        center0: Vector3<f32>,
+        center1: Vector3<f32>,
+        time0: f32,
+        time1: f32,
+        time: f32,
+    ) -> Vector3<f32> {
+        let duration = time1 - time0;
+        if duration.abs() < f32::EPSILON {
+            return center0;
+        }
+
+        let t = ((time - time0) / duration).clamp(0.0, 1.0);
+        center0 + (center1 - center0) * t.into()
+    }
+
     pub fn intersect(&self, ray: Ray) -> Option<Hit> {
         match *self {
             Object::Sphere {
@@ -69,6 +113,7 @@ impl Object {
                 Some(Hit {
                     position,
                     normal,
+                    front_face: ray.direction.dot(normal) < 0.0,
                     distance,
                 })
             }
@@ -93,9 +138,391 @@ impl Object {
                 Some(Hit {
                     position,
                     normal,
+                    front_face: ray.direction.dot(normal) < 0.0,
                     distance,
                 })
             }
+            Object::Triangle {
+                v0,
+                v1,
+                v2,
+                normal,
+                material: _,
+            } => {
+                const EPSILON: f32 = 0.0000001;
+
+                let e1 = v1 - v0;
+                let e2 = v2 - v0;
+
+                let p = ray.direction.cross(e2);
+                let det = e1.dot(p);
+                if det.abs() < EPSILON {
+                    return None;
+                }
+                let inv_det = 1.0 / det;
+
+                let t_vec = ray.origin - v0;
+                let u = t_vec.dot(p) * inv_det;
+                if u < 0.0 || u > 1.0 {
+                    return None;
+                }
+
+                let q = t_vec.cross(e1);
+                let v = ray.direction.dot(q) * inv_det;
+                if v < 0.0 || u + v > 1.0 {
+                    return None;
+                }
+
+                let distance = e2.dot(q) * inv_det;
+                if distance <= 0.0 {
+                    return None;
+                }
+
+                let position = ray.origin + ray.direction * distance.into();
+                Some(Hit {
+                    position,
+                    normal,
+                    front_face: ray.direction.dot(normal) < 0.0,
+                    distance,
+                })
+            }
+            Object::MovingSphere {
+                center0,
+                center1,
+                time0,
+                time1,
+                radius,
+                material: _,
+            } => {
+                let center = Self::moving_sphere_center(center0, center1, time0, time1, ray.time);
+
+                let oc = ray.origin - center;
+                let a = ray.direction.sqr_length();
+                let half_b = oc.dot(ray.direction);
+                let c = oc.sqr_length() - radius * radius;
+                let discriminant = half_b * half_b - a * c;
+
+                if discriminant < 0.0 {
+                    return None;
+                }
+
+                let distance = (-half_b - discriminant.sqrt()) / a;
+                if distance <= 0.0 {
+                    return None;
+                }
+
+                let position = ray.origin + ray.direction * distance.into();
+                let normal = (position - center) * (1.0 / radius).into();
+                Some(Hit {
+                    position,
+                    normal,
+                    front_face: ray.direction.dot(normal) < 0.0,
+                    distance,
+                })
+            }
+        }
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        match *self {
+            Object::Sphere {
+                center,
+                radius,
+                material: _,
+            } => {
+                let r = Vector3 {
+                    x: radius,
+                    y: radius,
+                    z: radius,
+                };
+                Aabb {
+                    min: center - r,
+                    max: center + r,
+                }
+            }
+            // Planes have no finite AABB; `Bvh::build` never calls this arm.
+            Object::Plane { .. } => unreachable!("planes are never inserted into the Bvh's tree"),
+            Object::Triangle {
+                v0,
+                v1,
+                v2,
+                normal: _,
+                material: _,
+            } => Aabb {
+                min: Vector3 {
+                    x: v0.x.min(v1.x).min(v2.x),
+                    y: v0.y.min(v1.y).min(v2.y),
+                    z: v0.z.min(v1.z).min(v2.z),
+                },
+                max: Vector3 {
+                    x: v0.x.max(v1.x).max(v2.x),
+                    y: v0.y.max(v1.y).max(v2.y),
+                    z: v0.z.max(v1.z).max(v2.z),
+                },
+            },
+            Object::MovingSphere {
+                center0,
+                center1,
+                radius,
+                material: _,
+                ..
+            } => {
+                // Bound the whole swept volume; a ray's `time` isn't known yet.
+                let r = Vector3 {
+                    x: radius,
+                    y: radius,
+                    z: radius,
+                };
+                Aabb {
+                    min: center0 - r,
+                    max: center0 + r,
+                }
+                .union(Aabb {
+                    min: center1 - r,
+                    max: center1 + r,
+                })
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Aabb {
+    pub min: Vector3<f32>,
+    pub max: Vector3<f32>,
+}
+
+impl Aabb {
+    pub fn union(self, other: Aabb) -> Aabb {
+        Aabb {
+            min: Vector3 {
+                x: self.min.x.min(other.min.x),
+                y: self.min.y.min(other.min.y),
+                z: self.min.z.min(other.min.z),
+            },
+            max: Vector3 {
+                x: self.max.x.max(other.max.x),
+                y: self.max.y.max(other.max.y),
+                z: self.max.z.max(other.max.z),
+            },
+        }
+    }
+
+    pub fn centroid(self) -> Vector3<f32> {
+        (self.min + self.max) * 0.5.into()
+    }
+
+    /// Slab-test ray/AABB intersection; `t_max` prunes hits farther than it.
+    pub fn intersect(self, ray: Ray, t_max: f32) -> bool {
+        let mut t_min = 0.0_f32;
+        let mut t_max = t_max;
+
+        for axis in 0..3 {
+            let (lo, hi, origin, direction) = match axis {
+                0 => (self.min.x, self.max.x, ray.origin.x, ray.direction.x),
+                1 => (self.min.y, self.max.y, ray.origin.y, ray.direction.y),
+                _ => (self.min.z, self.max.z, ray.origin.z, ray.direction.z),
+            };
+
+            let inv_direction = 1.0 / direction;
+            let mut t0 = (lo - origin) * inv_direction;
+            let mut t1 = (hi - origin) * inv_direction;
+            if t0 > t1 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+
+            t_min = t_min.max(t0);
+            t_max = t_max.min(t1);
+        }
+
+        t_max >= t_min && t_max > 0.0
+    }
+}
+
+enum BvhNode {
+    Leaf {
+        aabb: Aabb,
+        start: u32,
+        count: u32,
+    },
+    Internal {
+        aabb: Aabb,
+        left: u32,
+        right: u32,
+        /// 0 = x, 1 = y, 2 = z; the axis the centroid bounds were split on.
+        axis: u8,
+    },
+}
+
+impl BvhNode {
+    fn aabb(&self) -> Aabb {
+        match *self {
+            BvhNode::Leaf { aabb, .. } | BvhNode::Internal { aabb, .. } => aabb,
+        }
+    }
+}
+
+pub struct Bvh {
+    nodes: Vec<BvhNode>,
+    /// Object indices reordered so each leaf's objects are contiguous.
+    order: Vec<usize>,
+    root: usize,
+    /// Indices of objects with no finite AABB (`Object::Plane`); tested
+    /// directly on every query instead of being culled from the tree.
+    unbounded: Vec<usize>,
+}
+
+impl Bvh {
+    const LEAF_SIZE: usize = 4;
+
+    pub fn build(objects: &[Object]) -> Bvh {
+        let (unbounded, mut order): (Vec<usize>, Vec<usize>) = (0..objects.len())
+            .partition(|&index| matches!(objects[index], Object::Plane { .. }));
+        let mut nodes = Vec::new();
+
+        let root = if order.is_empty() {
+            0
+        } else {
+            let len = order.len();
+            Self::build_range(objects, &mut order, 0, len, &mut nodes)
+        };
+
+        Bvh {
+            nodes,
+            order,
+            root,
+            unbounded,
+        }
+    }
+
+    fn build_range(
+        objects: &[Object],
+        order: &mut [usize],
+        start: usize,
+        end: usize,
+        nodes: &mut Vec<BvhNode>,
+    ) -> usize {
+        let aabb = order[start..end]
+            .iter()
+            .map(|&index| objects[index].aabb())
+            .reduce(Aabb::union)
+            .expect("a BVH range is never built empty");
+
+        if end - start <= Self::LEAF_SIZE {
+            let node_index = nodes.len();
+            nodes.push(BvhNode::Leaf {
+                aabb,
+                start: start as u32,
+                count: (end - start) as u32,
+            });
+            return node_index;
+        }
+
+        let centroid_bounds = order[start..end]
+            .iter()
+            .map(|&index| {
+                let c = objects[index].aabb().centroid();
+                Aabb { min: c, max: c }
+            })
+            .reduce(Aabb::union)
+            .expect("a BVH range is never built empty");
+        let extent = centroid_bounds.max - centroid_bounds.min;
+        let axis: u8 = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+
+        let axis_value = |index: usize| -> f32 {
+            let c = objects[index].aabb().centroid();
+            match axis {
+                0 => c.x,
+                1 => c.y,
+                _ => c.z,
+            }
+        };
+
+        let mid = start + (end - start) / 2;
+        order[start..end]
+            .select_nth_unstable_by((end - start) / 2, |&a, &b| {
+                axis_value(a).total_cmp(&axis_value(b))
+            });
+
+        let left = Self::build_range(objects, order, start, mid, nodes);
+        let right = Self::build_range(objects, order, mid, end, nodes);
+
+        let node_index = nodes.len();
+        nodes.push(BvhNode::Internal {
+            aabb,
+            left: left as u32,
+            right: right as u32,
+            axis,
+        });
+        node_index
+    }
+
+    pub fn get_closest_object(&self, ray: Ray, objects: &[Object]) -> Option<(Hit, usize)> {
+        let mut closest: Option<(Hit, usize)> = None;
+
+        for &index in &self.unbounded {
+            if let Some(hit) = objects[index].intersect(ray) {
+                if closest.map_or(true, |(closest, _)| hit.distance < closest.distance) {
+                    closest = Some((hit, index));
+                }
+            }
+        }
+
+        if !self.nodes.is_empty() {
+            self.traverse(self.root, ray, objects, &mut closest);
+        }
+
+        closest
+    }
+
+    fn traverse(
+        &self,
+        node_index: usize,
+        ray: Ray,
+        objects: &[Object],
+        closest: &mut Option<(Hit, usize)>,
+    ) {
+        let node = &self.nodes[node_index];
+        let t_max = closest.map_or(f32::INFINITY, |(hit, _)| hit.distance);
+        if !node.aabb().intersect(ray, t_max) {
+            return;
+        }
+
+        match *node {
+            BvhNode::Leaf { start, count, .. } => {
+                for &index in &self.order[start as usize..(start + count) as usize] {
+                    if let Some(hit) = objects[index].intersect(ray) {
+                        if closest.map_or(true, |(closest, _)| hit.distance < closest.distance) {
+                            *closest = Some((hit, index));
+                        }
+                    }
+                }
+            }
+            BvhNode::Internal {
+                left, right, axis, ..
+            } => {
+                // Visit the nearer child first (front-to-back) so `closest`
+                // is as tight as possible by the time the far child is tested.
+                let direction_along_axis = match axis {
+                    0 => ray.direction.x,
+                    1 => ray.direction.y,
+                    _ => ray.direction.z,
+                };
+                let (near, far) = if direction_along_axis >= 0.0 {
+                    (left, right)
+                } else {
+                    (right, left)
+                };
+
+                self.traverse(near as usize, ray, objects, closest);
+                self.traverse(far as usize, ray, objects, closest);
+            }
         }
     }
 }
@@ -106,6 +533,10 @@ pub struct Camera {
     pub right: Vector3<f32>,
     pub up: Vector3<f32>,
     pub forward: Vector3<f32>,
+    pub aperture: f32,
+    pub focus_distance: f32,
+    pub shutter_open: f32,
+    pub shutter_close: f32,
 }
 
 impl Camera {
@@ -122,13 +553,50 @@ impl Camera {
         }
     }
 
-    pub fn get_ray(&self, uv: Vector2<f32>, aspect: f32) -> Ray {
+    pub fn get_ray(&self, uv: Vector2<f32>, aspect: f32, rng: &mut dyn rand::RngCore) -> Ray {
         Ray {
             origin: self.position,
             direction: ((self.right * ((uv.x * 2.0 - 1.0) * aspect).into())
                 + (self.up * (uv.y * 2.0 - 1.0).into())
                 + self.forward)
                 .normalized(),
+            time: self.shutter_open + rng.gen::<f32>() * (self.shutter_close - self.shutter_open),
+        }
+    }
+
+    pub fn get_ray_dof(
+        &self,
+        uv: Vector2<f32>,
+        aspect: f32,
+        rng: &mut dyn rand::RngCore,
+    ) -> Ray {
+        let pinhole = self.get_ray(uv, aspect, rng);
+
+        if self.aperture <= 0.0 {
+            return pinhole;
+        }
+
+        fn random_in_unit_disk(rng: &mut dyn rand::RngCore) -> (f32, f32) {
+            loop {
+                let x = rng.gen::<f32>() * 2.0 - 1.0;
+                let y = rng.gen::<f32>() * 2.0 - 1.0;
+                if x * x + y * y < 1.0 {
+                    return (x, y);
+                }
+            }
+        }
+
+        let lens_radius = self.aperture * 0.5;
+        let (x, y) = random_in_unit_disk(rng);
+        let offset = self.right * (x * lens_radius).into() + self.up * (y * lens_radius).into();
+
+        let origin = self.position + offset;
+        let focus_point = self.position + pinhole.direction * self.focus_distance.into();
+
+        Ray {
+            origin,
+            direction: (focus_point - origin).normalized(),
+            time: pinhole.time,
         }
     }
 }
@@ -136,29 +604,45 @@ impl Camera {
 pub const SAMPLES_PER_BOUNCE: usize = 2;
 pub const BOUNCES: usize = 5;
 pub const DAY: bool = false;
+pub const TONE_MAPPING: ToneMapping = ToneMapping::ACES;
 
-pub fn get_closest_object(ray: Ray, objects: &[Object]) -> Option<(Hit, usize)> {
-    objects
-        .iter()
-        .enumerate()
-        .fold(None, |hit, (index, object)| {
-            let new_hit = object.intersect(ray).map(|new_hit| (new_hit, index));
-            hit.zip(new_hit).map_or_else(
-                || hit.or(new_hit),
-                |(hit, new_hit)| {
-                    if hit.0.distance < new_hit.0.distance {
-                        Some(hit)
-                    } else {
-                        Some(new_hit)
-                    }
-                },
-            )
-        })
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ToneMapping {
+    None,
+    Reciprocal,
+    ACES,
+}
+
+impl ToneMapping {
+    pub fn apply(self, color: Vector3<f32>) -> Vector3<f32> {
+        fn map_components(color: Vector3<f32>, f: impl Fn(f32) -> f32) -> Vector3<f32> {
+            Vector3 {
+                x: f(color.x),
+                y: f(color.y),
+                z: f(color.z),
+            }
+        }
+
+        let mapped = match self {
+            ToneMapping::None => color,
+            ToneMapping::Reciprocal => map_components(color, |x| x / (1.0 + x)),
+            ToneMapping::ACES => {
+                map_components(color, |x| (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14))
+            }
+        };
+
+        map_components(mapped, |x| x.max(0.0).powf(1.0 / 2.2))
+    }
+}
+
+pub fn get_closest_object(ray: Ray, objects: &[Object], bvh: &Bvh) -> Option<(Hit, usize)> {
+    bvh.get_closest_object(ray, objects)
 }
 
 pub fn ray_trace(
     ray: Ray,
     objects: &[Object],
+    bvh: &Bvh,
     rng: &mut dyn rand::RngCore,
     depth: usize,
 ) -> Vector3<f32> {
@@ -166,34 +650,106 @@ pub fn ray_trace(
         return Vector3::zero();
     }
 
-    let hit = get_closest_object(ray, objects);
+    let hit = get_closest_object(ray, objects, bvh);
 
     if let Some((hit, index)) = hit {
-        fn random_in_direction(
+        fn cosine_weighted_hemisphere(
             rng: &mut dyn rand::RngCore,
-            direction: Vector3<f32>,
+            normal: Vector3<f32>,
         ) -> Vector3<f32> {
-            let random = Vector3 {
-                x: rng.gen::<f32>() * 2.0 - 1.0,
-                y: rng.gen::<f32>() * 2.0 - 1.0,
-                z: rng.gen::<f32>() * 2.0 - 1.0,
+            let r1 = rng.gen::<f32>();
+            let r2 = rng.gen::<f32>();
+            let phi = 2.0 * std::f32::consts::PI * r1;
+            let r = r2.sqrt();
+            let local = Vector3 {
+                x: r * phi.cos(),
+                y: r * phi.sin(),
+                z: (1.0 - r2).sqrt(),
             };
-            random * random.dot(direction).signum().into()
+
+            // Pick whichever world axis `normal` is least aligned with, so
+            // the cross product below can't degenerate.
+            let helper = if normal.x.abs() < normal.y.abs() && normal.x.abs() < normal.z.abs() {
+                Vector3 {
+                    x: 1.0,
+                    y: 0.0,
+                    z: 0.0,
+                }
+            } else if normal.y.abs() < normal.z.abs() {
+                Vector3 {
+                    x: 0.0,
+                    y: 1.0,
+                    z: 0.0,
+                }
+            } else {
+                Vector3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 1.0,
+                }
+            };
+            let tangent = helper.cross(normal).normalized();
+            let bitangent = normal.cross(tangent);
+
+            tangent * local.x.into() + bitangent * local.y.into() + normal * local.z.into()
+        }
+
+        fn refract(direction: Vector3<f32>, normal: Vector3<f32>, eta: f32) -> Option<Vector3<f32>> {
+            let cos_i = -direction.dot(normal);
+            let k = 1.0 - eta * eta * (1.0 - cos_i * cos_i);
+            if k < 0.0 {
+                None
+            } else {
+                Some(direction * eta.into() + normal * (eta * cos_i - k.sqrt()).into())
+            }
+        }
+
+        fn schlick(cos_i: f32, eta: f32) -> f32 {
+            let r0 = ((1.0 - eta) / (1.0 + eta)).powi(2);
+            r0 + (1.0 - r0) * (1.0 - cos_i).powi(5)
         }
 
         let material = objects[index].get_material();
-        let direction = ray.direction.reflect(hit.normal);
+
+        // Flip the geometric normal to face the ray for double-sided hits.
+        let normal = if hit.front_face {
+            hit.normal
+        } else {
+            hit.normal * (-1.0).into()
+        };
+
+        if let Some(ior) = material.dielectric {
+            let eta = if hit.front_face { 1.0 / ior } else { ior };
+            let cos_i = -ray.direction.dot(normal);
+
+            let direction = match refract(ray.direction, normal, eta) {
+                Some(refracted) if rng.gen::<f32>() > schlick(cos_i, eta) => refracted,
+                _ => ray.direction.reflect(normal),
+            };
+
+            let scattered = Ray {
+                origin: hit.position + direction * 0.001.into(),
+                direction,
+                time: ray.time,
+            };
+
+            return material.emit_color + ray_trace(scattered, objects, bvh, rng, depth - 1);
+        }
+
+        let mirror_direction = ray.direction.reflect(normal);
 
         let mut in_color: Vector3<f32> = Vector3::zero();
         for _ in 0..SAMPLES_PER_BOUNCE {
+            let diffuse_direction = cosine_weighted_hemisphere(rng, normal);
             in_color += ray_trace(
                 Ray {
-                    origin: hit.position + hit.normal * 0.001.into(),
-                    direction: random_in_direction(rng, direction)
-                        * (1.0 - material.reflectiveness).into()
-                        + direction * material.reflectiveness.into(),
+                    origin: hit.position + normal * 0.001.into(),
+                    direction: diffuse_direction * (1.0 - material.reflectiveness).into()
+                        + mirror_direction * material.reflectiveness.into(),
+                    time: ray.time,
                 },
                 objects,
+                bvh,
                 rng,
                 depth - 1,
             );
@@ -212,3 +768,91 @@ pub fn ray_trace(
         }
     }
 }
+
+pub fn load_obj(path: impl AsRef<Path>) -> Vec<Object> {
+    let (models, materials) = tobj::load_obj(
+        path.as_ref(),
+        &tobj::LoadOptions {
+            triangulate: true,
+            single_index: true,
+            ..Default::default()
+        },
+    )
+    .expect("failed to load obj file");
+    let materials = materials.expect("failed to load mtl file");
+
+    let default_material = Material {
+        diffuse_color: (0.8, 0.8, 0.8).into(),
+        emit_color: (0.0, 0.0, 0.0).into(),
+        reflectiveness: 0.0,
+        dielectric: None,
+    };
+
+    let materials: Vec<Material> = materials
+        .iter()
+        .map(|material| {
+            let diffuse_color = material.diffuse.unwrap_or([0.8, 0.8, 0.8]);
+            let emit_color = material.unknown_param.get("Ke").map_or([0.0; 3], |ke| {
+                let mut values = ke.split_whitespace().map(|value| value.parse().unwrap_or(0.0));
+                [
+                    values.next().unwrap_or(0.0),
+                    values.next().unwrap_or(0.0),
+                    values.next().unwrap_or(0.0),
+                ]
+            });
+            let specular = material.specular.unwrap_or([0.0, 0.0, 0.0]);
+            let shininess = material.shininess.unwrap_or(0.0);
+            let reflectiveness = (specular.iter().copied().fold(0.0_f32, f32::max)
+                * (shininess / 1000.0).min(1.0))
+            .clamp(0.0, 1.0);
+            // `Ni` above 1 marks a dielectric material.
+            let dielectric = material
+                .optical_density
+                .filter(|ior| *ior > 1.0 + f32::EPSILON);
+
+            Material {
+                diffuse_color: diffuse_color.into(),
+                emit_color: emit_color.into(),
+                reflectiveness,
+                dielectric,
+            }
+        })
+        .collect();
+
+    let mut objects = Vec::new();
+    for model in models {
+        let mesh = model.mesh;
+        let material = mesh
+            .material_id
+            .and_then(|id| materials.get(id))
+            .copied()
+            .unwrap_or(default_material);
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertex = |index: u32| -> Vector3<f32> {
+                let index = index as usize * 3;
+                (
+                    mesh.positions[index],
+                    mesh.positions[index + 1],
+                    mesh.positions[index + 2],
+                )
+                    .into()
+            };
+
+            let v0 = vertex(triangle[0]);
+            let v1 = vertex(triangle[1]);
+            let v2 = vertex(triangle[2]);
+            let normal = (v1 - v0).cross(v2 - v0).normalized();
+
+            objects.push(Object::Triangle {
+                v0,
+                v1,
+                v2,
+                normal,
+                material,
+            });
+        }
+    }
+
+    objects
+}